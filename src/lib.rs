@@ -32,6 +32,47 @@ impl From<TimerState> for Duration {
     }
 }
 
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+enum SoundId {
+    Woosh,
+    Bell,
+    Horn,
+    Chime,
+    Custom(String),
+}
+
+impl SoundId {
+    const BUILT_IN: [SoundId; 4] = [SoundId::Woosh, SoundId::Bell, SoundId::Horn, SoundId::Chime];
+
+    fn label(&self) -> &str {
+        match self {
+            SoundId::Woosh => "Woosh",
+            SoundId::Bell => "Bell",
+            SoundId::Horn => "Horn",
+            SoundId::Chime => "Chime",
+            SoundId::Custom(path) => path,
+        }
+    }
+}
+
+impl Default for SoundId {
+    fn default() -> Self {
+        SoundId::Woosh
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+enum Phase {
+    Work,
+    Rest,
+}
+
+impl Default for Phase {
+    fn default() -> Self {
+        Phase::Work
+    }
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 struct Timer {
@@ -40,7 +81,16 @@ struct Timer {
     displayed_time: u64,
     local_pause: bool,
     id: u32,
-    played_sound: bool,
+    fired: bool,
+    sound: SoundId,
+    #[serde(skip)]
+    custom_sound_path: String,
+    initial_duration: Duration,
+    rest_duration: Option<Duration>,
+    rest_sound: SoundId,
+    cycle: Phase,
+    repeats_remaining: Option<u32>,
+    initial_repeats: Option<u32>,
 }
 
 const BASE_TIME: u64 = 60;
@@ -53,11 +103,45 @@ impl Timer {
             state: TimerState::Paused(duration),
             displayed_time: 10,
             local_pause: false,
-            played_sound: false,
+            fired: false,
+            sound: SoundId::default(),
+            custom_sound_path: String::new(),
+            initial_duration: duration,
+            rest_duration: None,
+            rest_sound: SoundId::Bell,
+            cycle: Phase::Work,
+            repeats_remaining: None,
+            initial_repeats: None,
             id,
         }
     }
 
+    /// Advances a repeating (pomodoro-style) timer past an expired phase,
+    /// flipping `cycle` and restarting `state` for the next phase. Returns
+    /// the phase that was just entered, or `None` if this timer isn't
+    /// repeating (or has used up its `repeats_remaining`), in which case it
+    /// is left parked at zero like an ordinary timer.
+    fn advance_cycle(&mut self, now: Instant) -> Option<Phase> {
+        let rest_duration = self.rest_duration?;
+        match self.cycle {
+            Phase::Work => {
+                self.cycle = Phase::Rest;
+                self.state = TimerState::RunUntil(now + rest_duration);
+            }
+            Phase::Rest => {
+                if let Some(repeats) = &mut self.repeats_remaining {
+                    if *repeats == 0 {
+                        return None;
+                    }
+                    *repeats -= 1;
+                }
+                self.cycle = Phase::Work;
+                self.state = TimerState::RunUntil(now + self.initial_duration);
+            }
+        }
+        Some(self.cycle)
+    }
+
     fn remove_time(&mut self, removal_time: u64) {
         let d = Duration::from_secs(removal_time * BASE_TIME);
         match &mut self.state {
@@ -102,6 +186,21 @@ impl Timer {
     fn pause(&mut self, time_left: Duration) {
         self.state = TimerState::Paused(time_left);
     }
+
+    /// Resets the timer to its original (creation-time) duration, whether
+    /// it's currently paused, running, or already expired. A paused timer
+    /// stays paused; a running timer is simply rescheduled in place rather
+    /// than stopped.
+    fn reset(&mut self, now: Instant) {
+        self.state = if self.is_paused() {
+            TimerState::Paused(self.initial_duration)
+        } else {
+            TimerState::RunUntil(now + self.initial_duration)
+        };
+        self.cycle = Phase::Work;
+        self.fired = false;
+        self.repeats_remaining = self.initial_repeats;
+    }
 }
 
 impl Default for Timer {
@@ -112,80 +211,146 @@ impl Default for Timer {
 
 #[cfg(not(target_arch = "wasm32"))]
 mod audio {
+    use super::SoundId;
+
     pub struct Audio {
-        sink: Option<rodio::Sink>,
+        handle: Option<rodio::OutputStreamHandle>,
         _stream: Option<rodio::OutputStream>,
     }
 
     impl Audio {
         pub fn new() -> Self {
-            let (sink, _stream) = if let Ok((_stream, handle)) = rodio::OutputStream::try_default()
+            let (handle, _stream) = if let Ok((stream, handle)) = rodio::OutputStream::try_default()
             {
-                if let Ok(sink) = rodio::Sink::try_new(&handle) {
-                    (Some(sink), Some(_stream))
-                } else {
-                    (None, None)
-                }
+                (Some(handle), Some(stream))
             } else {
                 (None, None)
             };
-            Self { sink, _stream }
+            Self { handle, _stream }
         }
 
-        pub fn play(&self) {
-            if let Some(sink) = &self.sink {
-                if sink.empty() {
-                    let file = std::io::Cursor::new(
-                        include_bytes!("../assets/mixkit-wizard-fire-woosh-1326.wav").as_slice(),
-                    );
-                    let sound = rodio::Decoder::new(file).unwrap();
-                    sink.append(sound);
+        // Each call gets its own sink so overlapping expirations mix together
+        // instead of one sound blocking another.
+        pub fn play(&self, sound: &SoundId) {
+            let Some(handle) = &self.handle else { return };
+            let Ok(sink) = rodio::Sink::try_new(handle) else {
+                return;
+            };
+            let decoded = match sound {
+                SoundId::Woosh => Self::decode(include_bytes!(
+                    "../assets/mixkit-wizard-fire-woosh-1326.wav"
+                )),
+                SoundId::Bell => Self::decode(include_bytes!("../assets/bell.wav")),
+                SoundId::Horn => Self::decode(include_bytes!("../assets/horn.wav")),
+                SoundId::Chime => Self::decode(include_bytes!("../assets/chime.wav")),
+                SoundId::Custom(path) => {
+                    let Ok(file) = std::fs::File::open(path) else {
+                        return;
+                    };
+                    rodio::Decoder::new(std::io::BufReader::new(file)).ok()
                 }
+            };
+            if let Some(sound) = decoded {
+                sink.append(sound);
+                sink.detach();
             }
         }
+
+        fn decode(bytes: &'static [u8]) -> Option<rodio::Decoder<std::io::Cursor<&'static [u8]>>> {
+            rodio::Decoder::new(std::io::Cursor::new(bytes)).ok()
+        }
     }
 }
 
 #[cfg(target_arch = "wasm32")]
 mod audio {
+    use super::SoundId;
+
     pub struct Audio;
     impl Audio {
         pub fn new() -> Self {
             Self
         }
-        pub fn play(&self) {
-            if let Ok(sound) =
-                web_sys::HtmlAudioElement::new_with_src("mixkit-wizard-fire-woosh-1326.wav")
-            {
+        pub fn play(&self, sound: &SoundId) {
+            let src = match sound {
+                SoundId::Woosh => "mixkit-wizard-fire-woosh-1326.wav",
+                SoundId::Bell => "bell.wav",
+                SoundId::Horn => "horn.wav",
+                SoundId::Chime => "chime.wav",
+                SoundId::Custom(src) => src.as_str(),
+            };
+            if let Ok(sound) = web_sys::HtmlAudioElement::new_with_src(src) {
                 _ = sound.play();
             }
         }
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+mod notifications {
+    pub fn notify(title: &str, body: &str) {
+        _ = notify_rust::Notification::new()
+            .summary(title)
+            .body(body)
+            .show();
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod notifications {
+    pub fn notify(title: &str, body: &str) {
+        if web_sys::Notification::permission() == web_sys::NotificationPermission::Granted {
+            let opts = web_sys::NotificationOptions::new();
+            opts.set_body(body);
+            _ = web_sys::Notification::new_with_options(title, &opts);
+        } else {
+            // Ask for permission for next time; this expiry goes unannounced.
+            _ = web_sys::Notification::request_permission();
+        }
+    }
+}
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct MyApp {
     timers: Vec<Timer>,
     start_duration: u64,
+    rest_duration: u64,
+    rest_sound: SoundId,
+    repeats: u32,
     displayed_time: u64,
     new_name: String,
     next_timer_id: u32,
+    time_scale: f64,
+    notifications_enabled: bool,
+    timeline_view: bool,
     #[serde(skip)]
     running: bool,
     #[serde(skip)]
+    last_frame: Option<Instant>,
+    #[serde(skip)]
     audio: audio::Audio,
 }
 
+const MIN_TIME_SCALE: f64 = 0.25;
+const MAX_TIME_SCALE: f64 = 16.0;
+
 impl Default for MyApp {
     fn default() -> Self {
         Self {
             timers: vec![Timer::new("torch".to_owned(), 60, 0)],
             start_duration: 60,
+            rest_duration: 0,
+            rest_sound: SoundId::Bell,
+            repeats: 0,
             displayed_time: 10,
             new_name: "torch".to_owned(),
             next_timer_id: 0,
+            time_scale: 1.0,
+            notifications_enabled: false,
+            timeline_view: false,
             running: false,
+            last_frame: None,
             audio: audio::Audio::new(),
         }
     }
@@ -212,131 +377,277 @@ impl MyApp {
 
         Default::default()
     }
-}
 
-impl eframe::App for MyApp {
-    /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+    /// Fires the sound/notification/pomodoro-advance side effects for every
+    /// timer that just hit zero, regardless of which view is on screen.
+    /// Expiry is handled here rather than in `list_ui`/`timeline_ui`, so it
+    /// runs the same whichever view is shown. This is the only place that sets
+    /// `fired` and advances `cycle`; the list's "⏮" and "↺" buttons only clear
+    /// `fired` (and "↺" puts `cycle` back to `Work`).
+    fn fire_expirations(&mut self, now: Instant) {
+        for timer in &mut self.timers {
+            if !timer.time_remaining(now).is_zero() || timer.fired {
+                continue;
+            }
+            let cue = match timer.cycle {
+                Phase::Work => &timer.sound,
+                Phase::Rest => &timer.rest_sound,
+            };
+            self.audio.play(cue);
+            if self.notifications_enabled {
+                notifications::notify(&timer.name, "expired");
+            }
+            timer.fired = true;
+            if timer.advance_cycle(now).is_some() {
+                timer.fired = false;
+            }
+        }
     }
 
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            let now = Instant::now();
-            let mut timers_to_add = Vec::new();
-            let mut index = 0;
-            self.timers.retain_mut(|timer| {
-                index += 1;
-                let time_left = timer.time_remaining(now);
-                let mut ret = true;
-                ui.horizontal(|ui| {
-                    if ui.button("×").clicked() {
-                        ret = false;
-                        index -= 1;
-                    }
-                    ui.vertical(|ui| {
-                        let id = ui.make_persistent_id(timer.id);
-                        let mut state =
-                            egui::collapsing_header::CollapsingState::load_with_default_open(
-                                ui.ctx(),
-                                id,
-                                false,
-                            );
+    fn list_ui(&mut self, ui: &mut egui::Ui, now: Instant) {
+        let mut timers_to_add = Vec::new();
+        let mut index = 0;
+        self.timers.retain_mut(|timer| {
+            index += 1;
+            let time_left = timer.time_remaining(now);
+            let mut ret = true;
+            ui.horizontal(|ui| {
+                if ui.button("×").clicked() {
+                    ret = false;
+                    index -= 1;
+                }
+                ui.vertical(|ui| {
+                    let id = ui.make_persistent_id(timer.id);
+                    let mut state = egui::collapsing_header::CollapsingState::load_with_default_open(
+                        ui.ctx(),
+                        id,
+                        false,
+                    );
 
-                        ui.horizontal(|ui| {
-                            ui.text_edit_singleline(&mut timer.name);
-                            let time = time_left.as_secs();
-                            let minutes = time / 60;
-                            let seconds = time % 60;
-                            if time_left.is_zero() {
-                                if !timer.played_sound {
-                                    self.audio.play();
-                                    timer.played_sound = true;
-                                }
-                                ui.colored_label(egui::Color32::RED, "Done   ");
-                            } else {
-                                let text_time = format!("{minutes:0>2}:{seconds:0>2}");
-                                if ui.selectable_label(!timer.local_pause, text_time).clicked() {
-                                    if timer.local_pause {
-                                        if self.running {
-                                            timer.start(now);
-                                        }
-                                        timer.local_pause = false;
-                                    } else {
-                                        timer.pause(time_left);
-                                        timer.local_pause = true;
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut timer.name);
+                        let time = time_left.as_secs();
+                        let minutes = time / 60;
+                        let seconds = time % 60;
+                        if time_left.is_zero() {
+                            ui.colored_label(egui::Color32::RED, "Done   ");
+                        } else {
+                            let text_time = format!("{minutes:0>2}:{seconds:0>2}");
+                            if ui.selectable_label(!timer.local_pause, text_time).clicked() {
+                                if timer.local_pause {
+                                    if self.running {
+                                        timer.start(now);
                                     }
+                                    timer.local_pause = false;
+                                } else {
+                                    timer.pause(time_left);
+                                    timer.local_pause = true;
                                 }
                             }
-                            state.show_toggle_button(ui, circle_icon);
-                        });
+                        }
+                        state.show_toggle_button(ui, circle_icon);
+                    });
 
-                        let mut close = false;
-                        state.show_body_unindented(ui, |ui| {
-                            ui.horizontal(|ui| {
-                                ui.add(egui::DragValue::new(&mut timer.displayed_time));
-                                if ui.button("⏮").clicked() {
-                                    timer.add_time(timer.displayed_time);
-                                    if time_left.is_zero() && !timer.time_remaining(now).is_zero() {
-                                        timer.played_sound = false;
-                                    }
+                    let mut close = false;
+                    state.show_body_unindented(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.add(egui::DragValue::new(&mut timer.displayed_time));
+                            if ui.button("⏮").clicked() {
+                                timer.add_time(timer.displayed_time);
+                                if time_left.is_zero() && !timer.time_remaining(now).is_zero() {
+                                    timer.fired = false;
                                 }
-                                if ui
-                                    .add_enabled(!time_left.is_zero(), Button::new("⏭"))
-                                    .clicked()
-                                {
-                                    timer.remove_time(timer.displayed_time);
+                            }
+                            if ui
+                                .add_enabled(!time_left.is_zero(), Button::new("⏭"))
+                                .clicked()
+                            {
+                                timer.remove_time(timer.displayed_time);
+                            }
+                            if ui.button("=").clicked() {
+                                timer.set_time(timer.displayed_time);
+                            }
+                            if ui.button("↺").clicked() {
+                                timer.reset(now);
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_source(("sound", timer.id))
+                                .selected_text(timer.sound.label().to_owned())
+                                .show_ui(ui, |ui| {
+                                    for sound in SoundId::BUILT_IN {
+                                        let label = sound.label().to_owned();
+                                        ui.selectable_value(&mut timer.sound, sound, label);
+                                    }
+                                });
+                            ui.text_edit_singleline(&mut timer.custom_sound_path);
+                            if ui.button("Load file").clicked() {
+                                timer.sound = SoundId::Custom(timer.custom_sound_path.clone());
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.button("+").clicked() {
+                                let mut timer = Timer::new(
+                                    self.new_name.clone(),
+                                    self.start_duration,
+                                    self.next_timer_id,
+                                );
+                                self.next_timer_id = self.next_timer_id.wrapping_add(1);
+                                if self.rest_duration > 0 {
+                                    timer.rest_duration =
+                                        Some(Duration::from_secs(self.rest_duration * BASE_TIME));
                                 }
-                                if ui.button("=").clicked() {
-                                    timer.set_time(timer.displayed_time);
+                                timer.rest_sound = self.rest_sound.clone();
+                                timer.repeats_remaining =
+                                    (self.repeats > 0).then_some(self.repeats);
+                                timer.initial_repeats = timer.repeats_remaining;
+                                if self.running {
+                                    timer.start(now);
                                 }
-                            });
-                            ui.horizontal(|ui| {
-                                if ui.button("+").clicked() {
-                                    let mut timer = Timer::new(
-                                        self.new_name.clone(),
-                                        self.start_duration,
-                                        self.next_timer_id,
-                                    );
-                                    self.next_timer_id = self.next_timer_id.wrapping_add(1);
-                                    if self.running {
-                                        timer.start(now);
+                                timers_to_add.push((index, timer));
+                                close = true;
+                            }
+                            ui.text_edit_singleline(&mut self.new_name);
+                            ui.add(egui::DragValue::new(&mut self.start_duration));
+                            ui.add(egui::DragValue::new(&mut self.rest_duration));
+                            ui.add(egui::DragValue::new(&mut self.repeats).prefix("×"));
+                            egui::ComboBox::from_id_source("new_rest_sound")
+                                .selected_text(self.rest_sound.label().to_owned())
+                                .show_ui(ui, |ui| {
+                                    for sound in SoundId::BUILT_IN {
+                                        let label = sound.label().to_owned();
+                                        ui.selectable_value(&mut self.rest_sound, sound, label);
                                     }
-                                    timers_to_add.push((index, timer));
-                                    close = true;
-                                }
-                                ui.text_edit_singleline(&mut self.new_name);
-                                ui.add(egui::DragValue::new(&mut self.start_duration));
-                            });
+                                });
                         });
-                        if close {
-                            state.set_open(false);
-                            state.store(ui.ctx());
-                        }
                     });
+                    if close {
+                        state.set_open(false);
+                        state.store(ui.ctx());
+                    }
                 });
-                ret
             });
-            for (index, timer) in timers_to_add {
-                self.timers.insert(index, timer);
-            }
-            if self.timers.is_empty() {
-                ui.horizontal(|ui| {
-                    if ui.button("+").clicked() {
-                        let mut timer = Timer::new(
-                            self.new_name.clone(),
-                            self.start_duration,
-                            self.next_timer_id,
-                        );
-                        self.next_timer_id = self.next_timer_id.wrapping_add(1);
-                        if self.running {
-                            timer.start(now);
+            ret
+        });
+        for (index, timer) in timers_to_add {
+            self.timers.insert(index, timer);
+        }
+        if self.timers.is_empty() {
+            ui.horizontal(|ui| {
+                if ui.button("+").clicked() {
+                    let mut timer = Timer::new(
+                        self.new_name.clone(),
+                        self.start_duration,
+                        self.next_timer_id,
+                    );
+                    self.next_timer_id = self.next_timer_id.wrapping_add(1);
+                    if self.rest_duration > 0 {
+                        timer.rest_duration =
+                            Some(Duration::from_secs(self.rest_duration * BASE_TIME));
+                    }
+                    timer.rest_sound = self.rest_sound.clone();
+                    timer.repeats_remaining = (self.repeats > 0).then_some(self.repeats);
+                    timer.initial_repeats = timer.repeats_remaining;
+                    if self.running {
+                        timer.start(now);
+                    }
+                    self.timers.push(timer);
+                }
+                ui.text_edit_singleline(&mut self.new_name);
+                ui.add(egui::DragValue::new(&mut self.start_duration));
+                ui.add(egui::DragValue::new(&mut self.rest_duration));
+                ui.add(egui::DragValue::new(&mut self.repeats).prefix("×"));
+                egui::ComboBox::from_id_source("new_rest_sound_empty")
+                    .selected_text(self.rest_sound.label().to_owned())
+                    .show_ui(ui, |ui| {
+                        for sound in SoundId::BUILT_IN {
+                            let label = sound.label().to_owned();
+                            ui.selectable_value(&mut self.rest_sound, sound, label);
                         }
-                        self.timers.push(timer);
+                    });
+            });
+        }
+    }
+
+    /// Renders every timer as a horizontal bar on a shared time axis, so it's
+    /// easy to see at a glance which timer expires first and how much they
+    /// overlap. Fed from the same `Vec<Timer>` as `list_ui`, just displayed
+    /// differently.
+    fn timeline_ui(&mut self, ui: &mut egui::Ui, now: Instant) {
+        let max_remaining = self
+            .timers
+            .iter_mut()
+            .map(|timer| timer.time_remaining(now))
+            .max()
+            .unwrap_or(Duration::ZERO);
+        for timer in &mut self.timers {
+            let time_left = timer.time_remaining(now);
+            ui.horizontal(|ui| {
+                ui.add_sized([80.0, 18.0], egui::Label::new(&timer.name));
+                let (rect, _response) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), 18.0),
+                    egui::Sense::hover(),
+                );
+                ui.painter()
+                    .rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+                let fraction = if max_remaining.is_zero() {
+                    0.0
+                } else {
+                    time_left.as_secs_f32() / max_remaining.as_secs_f32()
+                };
+                let color = if time_left.is_zero() {
+                    egui::Color32::RED
+                } else if timer.is_paused() {
+                    egui::Color32::GRAY
+                } else {
+                    egui::Color32::GREEN
+                };
+                let bar = egui::Rect::from_min_size(
+                    rect.min,
+                    egui::vec2(rect.width() * fraction, rect.height()),
+                );
+                ui.painter().rect_filled(bar, 2.0, color);
+            });
+        }
+    }
+}
+
+impl eframe::App for MyApp {
+    /// Called by the frame work to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let now = Instant::now();
+            let dt = self
+                .last_frame
+                .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+            self.last_frame = Some(now);
+            if self.time_scale != 1.0 {
+                let skew = dt.mul_f64((self.time_scale - 1.0).abs());
+                for timer in &mut self.timers {
+                    if let TimerState::RunUntil(end) = &mut timer.state {
+                        *end = if self.time_scale > 1.0 {
+                            end.checked_sub(skew).unwrap_or(now)
+                        } else {
+                            *end + skew
+                        };
                     }
-                    ui.text_edit_singleline(&mut self.new_name);
-                    ui.add(egui::DragValue::new(&mut self.start_duration));
-                });
+                }
+            }
+            self.fire_expirations(now);
+            ui.horizontal(|ui| {
+                let label = if self.timeline_view { "☰" } else { "📊" };
+                if ui.button(label).clicked() {
+                    self.timeline_view = !self.timeline_view;
+                }
+            });
+            if self.timeline_view {
+                self.timeline_ui(ui, now);
+            } else {
+                self.list_ui(ui, now);
             }
             ui.separator();
             if !self.timers.is_empty() {
@@ -374,9 +685,22 @@ impl eframe::App for MyApp {
                         }
                         self.running = false;
                     }
+                    if ui.button("⏪").clicked() {
+                        self.time_scale = (self.time_scale / 2.0).max(MIN_TIME_SCALE);
+                    }
+                    ui.label(format!("{}×", self.time_scale));
+                    if ui.button("⏩").clicked() {
+                        self.time_scale = (self.time_scale * 2.0).min(MAX_TIME_SCALE);
+                    }
+                    ui.checkbox(&mut self.notifications_enabled, "🔔");
                 });
             }
-            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            let repaint_after = if self.time_scale > 1.0 {
+                Duration::from_secs_f64(1.0 / self.time_scale)
+            } else {
+                Duration::from_secs(1)
+            };
+            ctx.request_repaint_after(repaint_after);
         });
     }
 }